@@ -1,15 +1,17 @@
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 use tauri::{
     AppHandle, Emitter, Manager, State, WebviewUrl, WebviewWindowBuilder,
-    menu::{Menu, MenuItem},
+    menu::{Menu, MenuItem, PredefinedMenuItem, Submenu},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
 };
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
 
 // ============================================
 // 데이터 구조체
@@ -58,21 +60,226 @@ pub enum MemoEvent {
     Reloaded { memos: Vec<Memo> },
 }
 
+// 검색 결과 (BM25 점수 + 스니펫)
+#[derive(Debug, Serialize, Clone)]
+pub struct SearchHit {
+    id: String,
+    score: f64,
+    snippet: String,
+}
+
+// ============================================
+// 전문 검색 인덱스 (BM25)
+// ============================================
+
+// BM25 파라미터
+const BM25_K1: f64 = 1.2;
+const BM25_B: f64 = 0.75;
+
+/// 제목/본문을 소문자로 낮추고 비영숫자 기준으로 토큰화
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_lowercase())
+        .collect()
+}
+
+// 역색인: 메모 생성/수정/삭제 시 증분 갱신된다.
+#[derive(Default)]
+pub struct SearchIndex {
+    // term -> { memo_id -> 해당 메모에서의 term 빈도 }
+    postings: HashMap<String, HashMap<String, u32>>,
+    // memo_id -> 토큰 개수 (문서 길이)
+    doc_len: HashMap<String, usize>,
+}
+
+impl SearchIndex {
+    /// 메모 하나를 색인에 추가
+    fn add_document(&mut self, id: &str, title: &str, content: &str) {
+        let mut tokens = tokenize(title);
+        tokens.extend(tokenize(content));
+        self.doc_len.insert(id.to_string(), tokens.len());
+        for tok in tokens {
+            *self
+                .postings
+                .entry(tok)
+                .or_default()
+                .entry(id.to_string())
+                .or_insert(0) += 1;
+        }
+    }
+
+    /// 메모 하나를 색인에서 제거
+    fn remove_document(&mut self, id: &str) {
+        self.doc_len.remove(id);
+        self.postings.retain(|_, posting| {
+            posting.remove(id);
+            !posting.is_empty()
+        });
+    }
+
+    /// 전체 메모로부터 색인을 다시 만든다 (시작 시 / 핫 리로드 시)
+    fn rebuild(&mut self, memos: &[Memo]) {
+        self.postings.clear();
+        self.doc_len.clear();
+        for memo in memos {
+            self.add_document(&memo.id, &memo.title, &memo.content);
+        }
+    }
+
+    /// 평균 문서 길이
+    fn avgdl(&self) -> f64 {
+        if self.doc_len.is_empty() {
+            return 0.0;
+        }
+        let total: usize = self.doc_len.values().sum();
+        total as f64 / self.doc_len.len() as f64
+    }
+
+    /// 쿼리에 대한 memo_id -> BM25 점수
+    fn score(&self, query_terms: &[String]) -> HashMap<String, f64> {
+        let n_total = self.doc_len.len() as f64;
+        let avgdl = self.avgdl();
+        let mut scores: HashMap<String, f64> = HashMap::new();
+
+        for term in query_terms {
+            let Some(posting) = self.postings.get(term) else {
+                continue;
+            };
+            let n = posting.len() as f64;
+            let idf = ((n_total - n + 0.5) / (n + 0.5) + 1.0).ln();
+
+            for (id, &freq) in posting {
+                let f = freq as f64;
+                let dl = *self.doc_len.get(id).unwrap_or(&0) as f64;
+                let denom = f + BM25_K1 * (1.0 - BM25_B + BM25_B * dl / avgdl);
+                let contribution = idf * (f * (BM25_K1 + 1.0)) / denom;
+                *scores.entry(id.clone()).or_insert(0.0) += contribution;
+            }
+        }
+
+        scores
+    }
+}
+
+/// 매칭된 첫 번째 쿼리 term 주변을 잘라 스니펫을 만든다.
+fn build_snippet(memo: &Memo, query_terms: &[String]) -> String {
+    const RADIUS: usize = 40;
+    let source = if memo.content.trim().is_empty() {
+        &memo.title
+    } else {
+        &memo.content
+    };
+    // 쿼리 term은 소문자이므로 소문자 사본 위에서 매칭한다. 동시에 소문자 바이트
+    // 오프셋 → 원본 바이트 오프셋 매핑을 만들어, 스니펫은 원본(대소문자 유지)에서 자른다.
+    // (İ 같은 문자는 소문자화 시 길이가 달라 1:1 매핑이 성립하지 않으므로 매핑이 필요하다.)
+    let mut lower = String::new();
+    let mut offsets: Vec<(usize, usize)> = Vec::new();
+    for (src_idx, ch) in source.char_indices() {
+        offsets.push((lower.len(), src_idx));
+        for lc in ch.to_lowercase() {
+            lower.push(lc);
+        }
+    }
+
+    let best = query_terms
+        .iter()
+        .filter_map(|t| lower.find(t.as_str()))
+        .min();
+
+    match best {
+        Some(pos) => {
+            // 소문자 오프셋을 원본의 char 경계로 되돌린다.
+            let src_pos = offsets
+                .iter()
+                .rev()
+                .find(|(lo, _)| *lo <= pos)
+                .map(|(_, s)| *s)
+                .unwrap_or(0);
+            let start = source[..src_pos]
+                .char_indices()
+                .rev()
+                .nth(RADIUS)
+                .map(|(i, _)| i)
+                .unwrap_or(0);
+            let end = source[src_pos..]
+                .char_indices()
+                .nth(RADIUS)
+                .map(|(i, _)| src_pos + i)
+                .unwrap_or(source.len());
+            let mut snippet = source[start..end].trim().to_string();
+            if start > 0 {
+                snippet.insert_str(0, "…");
+            }
+            if end < source.len() {
+                snippet.push('…');
+            }
+            snippet
+        }
+        None => source.chars().take(RADIUS * 2).collect(),
+    }
+}
+
+// ============================================
+// 편집 히스토리 (undo/redo)
+// ============================================
+
+// 히스토리 최대 길이 및 연속 편집 병합 시간 창
+const HISTORY_LIMIT: usize = 100;
+const COALESCE_WINDOW: Duration = Duration::from_millis(800);
+
+// 편집 스냅샷 (제목/본문/색상)
+#[derive(Debug, Clone)]
+struct Snapshot {
+    title: String,
+    content: String,
+    color: String,
+}
+
+impl Snapshot {
+    fn from_memo(memo: &Memo) -> Self {
+        Self {
+            title: memo.title.clone(),
+            content: memo.content.clone(),
+            color: memo.color.clone(),
+        }
+    }
+}
+
+// 메모 하나의 undo/redo 스택
+#[derive(Default)]
+struct History {
+    undo: VecDeque<Snapshot>,
+    redo: Vec<Snapshot>,
+    last_push: Option<std::time::Instant>,
+}
+
 // ============================================
 // 앱 상태 (메모리 캐시)
 // ============================================
 
 pub struct AppState {
     memos: Mutex<Vec<Memo>>,
+    index: Mutex<SearchIndex>,
     save_pending: AtomicBool,
+    // 마지막으로 디스크에 쓴 내용의 해시. 파일 감시자가 자기 자신의
+    // 쓰기로 인한 이벤트를 무시하는 데 사용한다.
+    content_hash: Mutex<u64>,
+    // 메모별 undo/redo 히스토리
+    history: Mutex<HashMap<String, History>>,
 }
 
 impl AppState {
     fn new() -> Self {
         let memos = load_memos_from_file();
+        let mut index = SearchIndex::default();
+        index.rebuild(&memos);
         Self {
             memos: Mutex::new(memos),
+            index: Mutex::new(index),
             save_pending: AtomicBool::new(false),
+            content_hash: Mutex::new(file_content_hash()),
+            history: Mutex::new(HashMap::new()),
         }
     }
 }
@@ -93,20 +300,73 @@ fn get_data_path() -> PathBuf {
     data_dir.join("memos.json")
 }
 
+// 유지할 백업 개수 (memos.json.bak.1 .. .bak.N)
+const BACKUP_COUNT: usize = 5;
+
+// N번째 백업 경로 (1이 가장 최신)
+fn backup_path(n: usize) -> PathBuf {
+    let path = get_data_path();
+    path.with_extension(format!("json.bak.{}", n))
+}
+
 fn load_memos_from_file() -> Vec<Memo> {
     let path = get_data_path();
 
     if path.exists() {
-        match fs::read_to_string(&path) {
-            Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
-            Err(_) => Vec::new(),
+        if let Ok(content) = fs::read_to_string(&path) {
+            match serde_json::from_str(&content) {
+                Ok(memos) => return memos,
+                Err(e) => eprintln!("Primary memos.json failed to parse ({}), trying backups", e),
+            }
         }
-    } else {
-        Vec::new()
     }
+
+    // 기본 파일이 없거나 손상됨 → 가장 최신의 정상 백업으로 폴백
+    for n in 1..=BACKUP_COUNT {
+        let bak = backup_path(n);
+        if let Ok(content) = fs::read_to_string(&bak) {
+            if let Ok(memos) = serde_json::from_str(&content) {
+                eprintln!("Recovered memos from backup: {}", bak.display());
+                return memos;
+            }
+        }
+    }
+
+    Vec::new()
 }
 
-fn save_memos_to_file(memos: &Vec<Memo>) -> Result<(), String> {
+// 바이트 열의 해시 (파일 감시자 피드백 루프 방지용)
+fn hash_bytes(bytes: &[u8]) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
+
+// 현재 디스크 파일 내용의 해시 (없으면 0)
+fn file_content_hash() -> u64 {
+    match fs::read(get_data_path()) {
+        Ok(bytes) => hash_bytes(&bytes),
+        Err(_) => 0,
+    }
+}
+
+// 기존 기본 파일을 백업 링으로 회전시킨다 (.bak.5 폐기, 나머지 한 칸씩 밀기).
+fn rotate_backups(path: &Path) {
+    if !path.exists() {
+        return;
+    }
+    // 가장 오래된 백업 제거 후 차례로 한 칸씩 이동
+    fs::remove_file(backup_path(BACKUP_COUNT)).ok();
+    for n in (1..BACKUP_COUNT).rev() {
+        fs::rename(backup_path(n), backup_path(n + 1)).ok();
+    }
+    fs::rename(path, backup_path(1)).ok();
+}
+
+// 원자적 쓰기 + 롤링 백업. 쓴 내용의 해시를 반환한다.
+// 임시 파일에 먼저 쓰고, 직전 파일을 백업으로 회전시킨 뒤, rename으로 덮어쓴다.
+fn save_memos_to_file(memos: &[Memo]) -> Result<u64, String> {
     let path = get_data_path();
 
     // 개발 모드: pretty print, 프로덕션: compact
@@ -116,8 +376,15 @@ fn save_memos_to_file(memos: &Vec<Memo>) -> Result<(), String> {
     #[cfg(not(debug_assertions))]
     let json = serde_json::to_string(memos).map_err(|e| e.to_string())?;
 
-    fs::write(&path, json).map_err(|e| e.to_string())?;
-    Ok(())
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, &json).map_err(|e| e.to_string())?;
+
+    rotate_backups(&path);
+
+    // 같은 파일시스템 내 rename은 원자적이다.
+    fs::rename(&tmp, &path).map_err(|e| e.to_string())?;
+
+    Ok(hash_bytes(json.as_bytes()))
 }
 
 // 배치 저장 스케줄링 (500ms 디바운스) - 수정됨
@@ -130,8 +397,9 @@ fn schedule_save(state: &Arc<AppState>) {
             thread::sleep(Duration::from_millis(500));
             // 저장 시점에 최신 데이터 읽기
             let memos = state.memos.lock().unwrap().clone();
-            if let Err(e) = save_memos_to_file(&memos) {
-                eprintln!("Failed to save memos: {}", e);
+            match save_memos_to_file(&memos) {
+                Ok(hash) => *state.content_hash.lock().unwrap() = hash,
+                Err(e) => eprintln!("Failed to save memos: {}", e),
             }
             // 저장 완료 후 플래그 리셋
             state.save_pending.store(false, Ordering::SeqCst);
@@ -142,7 +410,8 @@ fn schedule_save(state: &Arc<AppState>) {
 // 즉시 저장 (상태 변경 후 플래그 리셋)
 fn save_immediately(state: &AppState) -> Result<(), String> {
     let memos = state.memos.lock().unwrap().clone();
-    save_memos_to_file(&memos)?;
+    let hash = save_memos_to_file(&memos)?;
+    *state.content_hash.lock().unwrap() = hash;
     state.save_pending.store(false, Ordering::SeqCst);
     Ok(())
 }
@@ -166,6 +435,45 @@ fn get_memo(state: State<SharedState>, id: String) -> Option<Memo> {
     state.memos.lock().unwrap().iter().find(|m| m.id == id).cloned()
 }
 
+/// 메모 전문 검색 (BM25 랭킹)
+///
+/// 빈 쿼리는 모든 메모를 `updated_at` 내림차순으로 반환한다.
+#[tauri::command]
+fn search_memos(state: State<SharedState>, query: String) -> Vec<SearchHit> {
+    let query_terms = tokenize(&query);
+
+    // 빈 쿼리: 전체 메모를 최신순으로
+    if query_terms.is_empty() {
+        let mut memos = state.memos.lock().unwrap().clone();
+        memos.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+        return memos
+            .iter()
+            .map(|m| SearchHit {
+                id: m.id.clone(),
+                score: 0.0,
+                snippet: build_snippet(m, &query_terms),
+            })
+            .collect();
+    }
+
+    let scores = state.index.lock().unwrap().score(&query_terms);
+    let memos = state.memos.lock().unwrap();
+
+    let mut hits: Vec<SearchHit> = scores
+        .into_iter()
+        .filter_map(|(id, score)| {
+            memos.iter().find(|m| m.id == id).map(|m| SearchHit {
+                id,
+                score,
+                snippet: build_snippet(m, &query_terms),
+            })
+        })
+        .collect();
+
+    hits.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    hits
+}
+
 /// 메모 생성
 #[tauri::command]
 fn create_memo(app: AppHandle, state: State<SharedState>, memo: Memo) -> Result<Memo, String> {
@@ -173,7 +481,12 @@ fn create_memo(app: AppHandle, state: State<SharedState>, memo: Memo) -> Result<
         let mut memos = state.memos.lock().unwrap();
         memos.insert(0, memo.clone());
     }
-    
+    state
+        .index
+        .lock()
+        .unwrap()
+        .add_document(&memo.id, &memo.title, &memo.content);
+
     // State<Arc<AppState>>에서 &Arc<AppState>로 변환
     schedule_save(state.inner());
     
@@ -183,20 +496,49 @@ fn create_memo(app: AppHandle, state: State<SharedState>, memo: Memo) -> Result<
     Ok(memo)
 }
 
-/// 메모 업데이트 (개별 필드)
-#[tauri::command]
-fn update_memo(
-    app: AppHandle,
-    state: State<SharedState>,
+// 메모 업데이트 핵심 로직. 커맨드와 창 이벤트 핸들러가 공유한다.
+fn apply_memo_update(
+    app: &AppHandle,
+    state: &SharedState,
     id: String,
     update: MemoUpdate,
 ) -> Result<Memo, String> {
     let updated_memo: Memo;
-    
+
+    // 텍스트(제목/본문/색상)가 바뀔 때만 편집 전 스냅샷을 히스토리에 쌓는다.
+    // 지오메트리 전용 업데이트는 히스토리를 만들지 않는다.
+    let touches_text =
+        update.title.is_some() || update.content.is_some() || update.color.is_some();
+    if touches_text {
+        let pre = {
+            let memos = state.memos.lock().unwrap();
+            memos.iter().find(|m| m.id == id).map(Snapshot::from_memo)
+        };
+        if let Some(snapshot) = pre {
+            let mut hist = state.history.lock().unwrap();
+            let h = hist.entry(id.clone()).or_default();
+            let now = std::time::Instant::now();
+            // 짧은 시간 내 연속 편집은 하나의 히스토리 항목으로 병합한다.
+            let coalesce = h
+                .last_push
+                .map(|t| now.duration_since(t) < COALESCE_WINDOW)
+                .unwrap_or(false);
+            if !coalesce {
+                h.undo.push_back(snapshot);
+                if h.undo.len() > HISTORY_LIMIT {
+                    h.undo.pop_front();
+                }
+            }
+            h.last_push = Some(now);
+            // 새 편집은 redo 스택을 무효화한다.
+            h.redo.clear();
+        }
+    }
+
     {
         let mut memos = state.memos.lock().unwrap();
         let memo = memos.iter_mut().find(|m| m.id == id);
-        
+
         match memo {
             Some(m) => {
                 if let Some(title) = update.title {
@@ -211,25 +553,148 @@ fn update_memo(
                 if let Some(window) = update.window {
                     m.window = Some(window);
                 }
-                m.updated_at = std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap()
-                    .as_millis() as u64;
-                
+                // 지오메트리 전용 저장은 마지막 편집 시각을 보존한다
+                // (창 이동/크기 변경이 메모를 최신순 목록 맨 위로 끌어올리지 않도록).
+                if touches_text {
+                    m.updated_at = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .unwrap()
+                        .as_millis() as u64;
+                }
+
                 updated_memo = m.clone();
             }
             None => return Err(format!("Memo not found: {}", id)),
         }
     }
-    
-    schedule_save(state.inner());
-    
-    // 이벤트 발행
+
+    // 지오메트리 전용 업데이트(창 이동/크기 변경)는 제목/본문이 그대로이므로
+    // 재색인·이벤트 발행을 건너뛴다. 드래그 중 인덱스/프론트엔드가 폭주하는 것을 막는다.
+    if touches_text {
+        let mut index = state.index.lock().unwrap();
+        index.remove_document(&id);
+        index.add_document(&id, &updated_memo.title, &updated_memo.content);
+    }
+
+    schedule_save(state);
+
+    if touches_text {
+        app.emit("memo-changed", MemoEvent::Updated { memo: updated_memo.clone() }).ok();
+    }
+
+    Ok(updated_memo)
+}
+
+/// 메모 업데이트 (개별 필드)
+#[tauri::command]
+fn update_memo(
+    app: AppHandle,
+    state: State<SharedState>,
+    id: String,
+    update: MemoUpdate,
+) -> Result<Memo, String> {
+    apply_memo_update(&app, state.inner(), id, update)
+}
+
+// 스냅샷을 메모에 적용하고 updated_at 갱신 + 재색인 + 저장 + 이벤트 발행.
+fn apply_snapshot(
+    app: &AppHandle,
+    state: &SharedState,
+    id: &str,
+    snap: Snapshot,
+) -> Result<Memo, String> {
+    let updated_memo: Memo;
+    {
+        let mut memos = state.memos.lock().unwrap();
+        let m = memos
+            .iter_mut()
+            .find(|m| m.id == id)
+            .ok_or_else(|| format!("Memo not found: {}", id))?;
+        m.title = snap.title;
+        m.content = snap.content;
+        m.color = snap.color;
+        m.updated_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        updated_memo = m.clone();
+    }
+
+    {
+        let mut index = state.index.lock().unwrap();
+        index.remove_document(id);
+        index.add_document(id, &updated_memo.title, &updated_memo.content);
+    }
+
+    schedule_save(state);
     app.emit("memo-changed", MemoEvent::Updated { memo: updated_memo.clone() }).ok();
-    
+
     Ok(updated_memo)
 }
 
+/// 직전 편집 취소 (undo)
+#[tauri::command]
+fn undo_memo(app: AppHandle, state: State<SharedState>, id: String) -> Result<Memo, String> {
+    let snap = {
+        let mut hist = state.history.lock().unwrap();
+        let h = hist
+            .get_mut(&id)
+            .ok_or_else(|| format!("No history for memo: {}", id))?;
+        h.undo.pop_back().ok_or_else(|| "Nothing to undo".to_string())?
+    };
+
+    // 현재 상태를 redo 스택으로 옮긴다.
+    let current = {
+        let memos = state.memos.lock().unwrap();
+        memos
+            .iter()
+            .find(|m| m.id == id)
+            .map(Snapshot::from_memo)
+            .ok_or_else(|| format!("Memo not found: {}", id))?
+    };
+    {
+        let mut hist = state.history.lock().unwrap();
+        let h = hist.entry(id.clone()).or_default();
+        h.redo.push(current);
+        h.last_push = None;
+    }
+
+    apply_snapshot(&app, state.inner(), &id, snap)
+}
+
+/// 취소한 편집 다시 실행 (redo)
+#[tauri::command]
+fn redo_memo(app: AppHandle, state: State<SharedState>, id: String) -> Result<Memo, String> {
+    let snap = {
+        let mut hist = state.history.lock().unwrap();
+        let h = hist
+            .get_mut(&id)
+            .ok_or_else(|| format!("No history for memo: {}", id))?;
+        h.redo.pop().ok_or_else(|| "Nothing to redo".to_string())?
+    };
+
+    // 현재 상태를 undo 스택으로 되돌린다 (병합 없이).
+    let current = {
+        let memos = state.memos.lock().unwrap();
+        memos
+            .iter()
+            .find(|m| m.id == id)
+            .map(Snapshot::from_memo)
+            .ok_or_else(|| format!("Memo not found: {}", id))?
+    };
+    {
+        let mut hist = state.history.lock().unwrap();
+        let h = hist.entry(id.clone()).or_default();
+        h.undo.push_back(current);
+        if h.undo.len() > HISTORY_LIMIT {
+            h.undo.pop_front();
+        }
+        h.last_push = None;
+    }
+
+    apply_snapshot(&app, state.inner(), &id, snap)
+}
+
 /// 메모 삭제
 #[tauri::command]
 fn delete_memo(app: AppHandle, state: State<SharedState>, id: String) -> Result<(), String> {
@@ -242,7 +707,9 @@ fn delete_memo(app: AppHandle, state: State<SharedState>, id: String) -> Result<
             return Err(format!("Memo not found: {}", id));
         }
     }
-    
+    state.index.lock().unwrap().remove_document(&id);
+    state.history.lock().unwrap().remove(&id);
+
     save_immediately(&state)?;
     
     // 이벤트 발행
@@ -277,21 +744,89 @@ async fn open_memo_window(app: AppHandle, memo_id: String) -> Result<(), String>
     #[cfg(not(debug_assertions))]
     let url = WebviewUrl::App(format!("index.html?window=memo&id={}", memo_id).into());
 
-    WebviewWindowBuilder::new(&app, &label, url)
+    // 저장된 창 상태가 있으면 위치/크기/항상 위 플래그를 복원한다.
+    let saved = {
+        let state = app.state::<SharedState>();
+        let memos = state.memos.lock().unwrap();
+        memos
+            .iter()
+            .find(|m| m.id == memo_id)
+            .and_then(|m| m.window.clone())
+    };
+
+    let builder = WebviewWindowBuilder::new(&app, &label, url)
         .title("메모")
-        .inner_size(300.0, 350.0)
         .min_inner_size(200.0, 150.0)
         .decorations(false)
         .transparent(false)
-        .always_on_top(false)
         .resizable(true)
-        .visible(true)
-        .build()
-        .map_err(|e| e.to_string())?;
+        .visible(true);
+
+    let builder = match &saved {
+        Some(ws) => builder
+            .inner_size(ws.width, ws.height)
+            .position(ws.x, ws.y)
+            .always_on_top(ws.always_on_top),
+        None => builder.inner_size(300.0, 350.0).always_on_top(false),
+    };
+
+    builder.build().map_err(|e| e.to_string())?;
 
     Ok(())
 }
 
+// memo-* 창의 현재 위치/크기를 WindowState로 저장한다 (update_memo 경유).
+fn persist_window_geometry(window: &tauri::Window) {
+    let Some(id) = window.label().strip_prefix("memo-") else {
+        return;
+    };
+    let id = id.to_string();
+    let app = window.app_handle();
+    let state = app.state::<SharedState>();
+
+    // 기존 저장값에서 always_on_top 플래그를 보존한다.
+    let prev = {
+        let memos = state.memos.lock().unwrap();
+        memos
+            .iter()
+            .find(|m| m.id == id)
+            .and_then(|m| m.window.clone())
+    };
+
+    let scale = window.scale_factor().unwrap_or(1.0);
+    let pos = window.outer_position().ok();
+    let size = window.inner_size().ok();
+
+    let ws = WindowState {
+        is_open: true,
+        x: pos
+            .map(|p| p.x as f64 / scale)
+            .or_else(|| prev.as_ref().map(|w| w.x))
+            .unwrap_or(0.0),
+        y: pos
+            .map(|p| p.y as f64 / scale)
+            .or_else(|| prev.as_ref().map(|w| w.y))
+            .unwrap_or(0.0),
+        width: size
+            .map(|s| s.width as f64 / scale)
+            .or_else(|| prev.as_ref().map(|w| w.width))
+            .unwrap_or(300.0),
+        height: size
+            .map(|s| s.height as f64 / scale)
+            .or_else(|| prev.as_ref().map(|w| w.height))
+            .unwrap_or(350.0),
+        always_on_top: prev.as_ref().map(|w| w.always_on_top).unwrap_or(false),
+    };
+
+    let update = MemoUpdate {
+        title: None,
+        content: None,
+        color: None,
+        window: Some(ws),
+    };
+    apply_memo_update(app, state.inner(), id, update).ok();
+}
+
 #[tauri::command]
 async fn close_memo_window(app: AppHandle, memo_id: String) -> Result<(), String> {
     let label = format!("memo-{}", memo_id);
@@ -312,6 +847,195 @@ async fn show_main_window(app: AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+// ============================================
+// 파일 감시자 (외부 변경 핫 리로드)
+// ============================================
+
+// memos.json 외부 변경을 감지해 캐시를 다시 읽고 모든 창에 알린다.
+// 싱크 도구/다른 기기/두 번째 인스턴스가 파일을 바꿔도 잃지 않도록 한다.
+fn start_file_watcher(app: AppHandle, state: SharedState) {
+    use notify::Watcher;
+
+    thread::spawn(move || {
+        let path = get_data_path();
+        // 원자적 쓰기(rename)도 감지하려면 파일이 아니라 상위 디렉터리를 감시한다.
+        let dir = path.parent().map(PathBuf::from).unwrap_or_else(|| PathBuf::from("."));
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut watcher = match notify::recommended_watcher(tx) {
+            Ok(w) => w,
+            Err(e) => {
+                eprintln!("Failed to create file watcher: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = watcher.watch(&dir, notify::RecursiveMode::NonRecursive) {
+            eprintln!("Failed to watch data dir: {}", e);
+            return;
+        }
+
+        for res in rx {
+            let Ok(event) = res else { continue };
+            if !event.paths.iter().any(|p| p == &path) {
+                continue;
+            }
+            reload_from_disk(&app, &state);
+        }
+    });
+}
+
+// 디스크의 현재 내용을 캐시로 다시 읽어 들인다 (피드백 루프 방지 포함).
+fn reload_from_disk(app: &AppHandle, state: &SharedState) {
+    // 우리 자신의 쓰기가 진행 중이면 무시
+    if state.save_pending.load(Ordering::SeqCst) {
+        return;
+    }
+
+    let Ok(bytes) = fs::read(get_data_path()) else {
+        return;
+    };
+    let hash = hash_bytes(&bytes);
+    // 방금 우리가 쓴 내용과 같으면 무시
+    if hash == *state.content_hash.lock().unwrap() {
+        return;
+    }
+
+    let memos: Vec<Memo> = match serde_json::from_slice(&bytes) {
+        Ok(m) => m,
+        Err(e) => {
+            eprintln!("Ignoring external change (parse error): {}", e);
+            return;
+        }
+    };
+
+    *state.memos.lock().unwrap() = memos.clone();
+    state.index.lock().unwrap().rebuild(&memos);
+    *state.content_hash.lock().unwrap() = hash;
+
+    app.emit("memo-changed", MemoEvent::Reloaded { memos }).ok();
+}
+
+// ============================================
+// 공용 액션 (트레이 / 메뉴 / 단축키 공유)
+// ============================================
+
+// 새 메모를 만들고 저장한 뒤 전용 창을 연다.
+// 트레이, 애플리케이션 메뉴, 전역 단축키가 모두 이 구현을 공유한다.
+fn create_new_memo(app: &AppHandle) {
+    let memo_id = uuid::Uuid::new_v4().to_string();
+    let new_memo = Memo {
+        id: memo_id.clone(),
+        title: "새 메모".to_string(),
+        content: String::new(),
+        color: "yellow".to_string(),
+        updated_at: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64,
+        window: None,
+    };
+
+    let state = app.state::<SharedState>();
+    {
+        let mut memos = state.memos.lock().unwrap();
+        memos.insert(0, new_memo.clone());
+    }
+    state
+        .index
+        .lock()
+        .unwrap()
+        .add_document(&new_memo.id, &new_memo.title, &new_memo.content);
+    if let Err(e) = save_immediately(&state) {
+        eprintln!("Failed to save memo: {}", e);
+    }
+
+    app.emit("memo-changed", MemoEvent::Created { memo: new_memo }).ok();
+
+    let app_clone = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = open_memo_window(app_clone, memo_id).await {
+            eprintln!("Failed to open memo window: {}", e);
+        }
+    });
+}
+
+// 메인 목록 창을 표시/포커스 (없으면 무시).
+fn show_main_list(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        window.show().ok();
+        window.set_focus().ok();
+    }
+}
+
+// 저장을 보장한 뒤 앱을 종료한다.
+fn quit_with_save(app: &AppHandle) {
+    let state = app.state::<SharedState>();
+    save_immediately(&state).ok();
+    app.exit(0);
+}
+
+// ============================================
+// 애플리케이션 메뉴
+// ============================================
+
+// 네이티브 앱 메뉴를 구성한다. 단축키는 레이블에 함께 노출되어 발견성을 높인다.
+//
+// 새 메모 / 목록 토글은 전역 단축키(setup_global_shortcuts)로 처리하므로 메뉴에는
+// 가속기를 달지 않고 키 조합을 레이블에만 표기한다 — 가속기까지 달면 창이 포커스된
+// 동안 전역 단축키와 함께 두 번 발동한다. 종료는 반대로 메뉴 가속기로만 둔다
+// (Cmd/Ctrl+Q를 전역으로 잡으면 다른 앱의 OS 표준 종료를 가로채기 때문).
+fn setup_menu(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+    let new_i = MenuItem::with_id(app, "menu_new", "새 메모 (Ctrl+N)", true, None::<&str>)?;
+    let show_i =
+        MenuItem::with_id(app, "menu_show", "메모 목록 (Ctrl+Shift+M)", true, None::<&str>)?;
+    let quit_i = MenuItem::with_id(app, "menu_quit", "종료", true, Some("CmdOrCtrl+Q"))?;
+
+    let memo_menu = Submenu::with_items(
+        app,
+        "메모",
+        true,
+        &[&new_i, &show_i, &PredefinedMenuItem::separator(app)?, &quit_i],
+    )?;
+
+    let menu = Menu::with_items(app, &[&memo_menu])?;
+    app.set_menu(menu)?;
+
+    app.on_menu_event(|app, event| match event.id.as_ref() {
+        "menu_new" => create_new_memo(app),
+        "menu_show" => show_main_list(app),
+        "menu_quit" => quit_with_save(app),
+        _ => {}
+    });
+
+    Ok(())
+}
+
+// 핵심 메모 액션에 대한 전역 단축키를 등록한다.
+// 종료(Cmd/Ctrl+Q)는 OS 표준 단축키를 가로채지 않도록 전역으로 잡지 않고,
+// 메뉴 가속기로만 둔다 (setup_menu 참고).
+fn setup_global_shortcuts(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+    let gs = app.global_shortcut();
+
+    gs.on_shortcut("CmdOrCtrl+N", |app, _shortcut, event| {
+        if let ShortcutState::Pressed = event.state() {
+            create_new_memo(app);
+        }
+    })?;
+    gs.on_shortcut("CmdOrCtrl+Shift+M", |app, _shortcut, event| {
+        if let ShortcutState::Pressed = event.state() {
+            // 목록 창이 보이면 숨기고, 아니면 표시/포커스한다.
+            match app.get_webview_window("main") {
+                Some(window) if window.is_visible().unwrap_or(false) => {
+                    window.hide().ok();
+                }
+                _ => show_main_list(app),
+            }
+        }
+    })?;
+
+    Ok(())
+}
+
 // ============================================
 // 시스템 트레이
 // ============================================
@@ -329,54 +1053,9 @@ fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
         .show_menu_on_left_click(false)
         .on_menu_event(|app, event| {
             match event.id.as_ref() {
-                "show" => {
-                    if let Some(window) = app.get_webview_window("main") {
-                        window.show().ok();
-                        window.set_focus().ok();
-                    }
-                }
-                "new" => {
-                    // 새 메모 생성
-                    let memo_id = uuid::Uuid::new_v4().to_string();
-                    let new_memo = Memo {
-                        id: memo_id.clone(),
-                        title: "새 메모".to_string(),
-                        content: String::new(),
-                        color: "yellow".to_string(),
-                        updated_at: std::time::SystemTime::now()
-                            .duration_since(std::time::UNIX_EPOCH)
-                            .unwrap()
-                            .as_millis() as u64,
-                        window: None,
-                    };
-
-                    // AppState를 통해 메모 저장
-                    let state = app.state::<SharedState>();
-                    {
-                        let mut memos = state.memos.lock().unwrap();
-                        memos.insert(0, new_memo.clone());
-                    }
-                    if let Err(e) = save_immediately(&state) {
-                        eprintln!("Failed to save memo: {}", e);
-                    }
-
-                    // 이벤트 발행
-                    app.emit("memo-changed", MemoEvent::Created { memo: new_memo }).ok();
-
-                    // 새 창 열기
-                    let app_clone = app.clone();
-                    tauri::async_runtime::spawn(async move {
-                        if let Err(e) = open_memo_window(app_clone, memo_id).await {
-                            eprintln!("Failed to open memo window: {}", e);
-                        }
-                    });
-                }
-                "quit" => {
-                    // 종료 전 저장 보장
-                    let state = app.state::<SharedState>();
-                    save_immediately(&state).ok();
-                    app.exit(0);
-                }
+                "show" => show_main_list(app),
+                "new" => create_new_memo(app),
+                "quit" => quit_with_save(app),
                 _ => {}
             }
         })
@@ -407,12 +1086,16 @@ fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_global_shortcut::Builder::new().build())
         .manage(Arc::new(AppState::new()))
         .invoke_handler(tauri::generate_handler![
             load_memos,
             get_memo,
+            search_memos,
             create_memo,
             update_memo,
+            undo_memo,
+            redo_memo,
             delete_memo,
             open_memo_window,
             close_memo_window,
@@ -420,6 +1103,9 @@ pub fn run() {
         ])
         .setup(|app| {
             setup_tray(app.handle())?;
+            setup_menu(app.handle())?;
+            setup_global_shortcuts(app.handle())?;
+            start_file_watcher(app.handle().clone(), app.state::<SharedState>().inner().clone());
             Ok(())
         })
         .on_window_event(|window, event| {
@@ -429,8 +1115,77 @@ pub fn run() {
                     window.hide().ok();
                     api.prevent_close();
                 }
+                return;
+            }
+
+            // memo-* 창: 이동/크기 변경/닫기 시 지오메트리를 저장한다.
+            if window.label().starts_with("memo-") {
+                match event {
+                    tauri::WindowEvent::Moved(_)
+                    | tauri::WindowEvent::Resized(_)
+                    | tauri::WindowEvent::CloseRequested { .. } => {
+                        persist_window_geometry(window);
+                    }
+                    _ => {}
+                }
             }
         })
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+// ============================================
+// 테스트
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn memo(id: &str, title: &str, content: &str) -> Memo {
+        Memo {
+            id: id.to_string(),
+            title: title.to_string(),
+            content: content.to_string(),
+            color: "yellow".to_string(),
+            updated_at: 0,
+            window: None,
+        }
+    }
+
+    #[test]
+    fn tokenize_lowercases_and_splits_on_non_alphanumeric() {
+        assert_eq!(tokenize("Hello, World! 123"), vec!["hello", "world", "123"]);
+        assert!(tokenize("   ...   ").is_empty());
+    }
+
+    #[test]
+    fn bm25_ranks_only_matching_documents() {
+        let memos = vec![
+            memo("a", "Rust memo", "learning rust today"),
+            memo("b", "Apple", "fruit salad"),
+        ];
+        let mut index = SearchIndex::default();
+        index.rebuild(&memos);
+
+        let scores = index.score(&tokenize("rust"));
+        assert!(scores.get("a").copied().unwrap_or(0.0) > 0.0);
+        assert!(!scores.contains_key("b"));
+    }
+
+    #[test]
+    fn build_snippet_handles_multibyte_without_panic() {
+        // U+0130(İ)은 소문자화 시 길이가 늘어 소문자 오프셋이 원본과 어긋난다.
+        // 예전에는 이 입력이 char 경계 패닉을 일으켰다 (회귀 방지).
+        let m = memo("a", "", "İ한글 메모 본문");
+        let snippet = build_snippet(&m, &tokenize("한글"));
+        assert!(snippet.contains("한글"));
+    }
+
+    #[test]
+    fn build_snippet_preserves_original_case() {
+        let m = memo("a", "", "Hello World");
+        let snippet = build_snippet(&m, &tokenize("world"));
+        assert!(snippet.contains("World"));
+    }
+}